@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use glob::Pattern;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::hasher::Hasher;
+
+/// Options controlling how `hash_directory` walks and filters a tree.
+#[derive(Default)]
+pub struct ChecksumOptions {
+    pub exclude: Vec<String>,
+    pub ignore_hidden: bool,
+    pub follow_symlinks: bool,
+}
+
+/// Collapses an entire directory tree into one stable hash.
+///
+/// Files are walked, filtered, then sorted by their path relative to `root`
+/// before being folded into the final digest, so the result is independent
+/// of filesystem iteration order: two trees with identical content (but
+/// different mtimes or directory-entry order) always produce the same value.
+pub fn hash_directory<P: AsRef<Path>>(
+    root: P,
+    hasher: &Hasher,
+    options: &ChecksumOptions,
+) -> Result<String> {
+    let root = root.as_ref();
+    let patterns = options
+        .exclude
+        .iter()
+        .map(|pattern| Pattern::new(pattern).context("Invalid --exclude glob"))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut entries: Vec<(String, PathBuf)> = WalkDir::new(root)
+        .follow_links(options.follow_symlinks)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let path = entry.path().to_path_buf();
+            let rel = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            let rel_str = rel.to_string_lossy().replace('\\', "/");
+            (rel_str, path)
+        })
+        .filter(|(rel_str, _)| {
+            if options.ignore_hidden && rel_str.split('/').any(|part| part.starts_with('.')) {
+                return false;
+            }
+            !patterns.iter().any(|pattern| pattern.matches(rel_str))
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let pieces = entries
+        .into_iter()
+        .map(|(rel_str, path)| {
+            let file_hash = hasher.hash_file(&path)?;
+            Ok(format!("{}  {}\n", file_hash, rel_str))
+        })
+        .collect::<Result<Vec<String>>>()?;
+
+    Ok(hasher.hash_sequence(pieces))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hasher::HashAlgorithm;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn make_tree(root: &Path) {
+        fs::write(root.join("a.txt"), b"aaa").unwrap();
+        fs::create_dir(root.join("sub")).unwrap();
+        fs::write(root.join("sub").join("b.txt"), b"bbb").unwrap();
+    }
+
+    #[test]
+    fn test_hash_directory_is_order_independent() {
+        let dir1 = tempdir().unwrap();
+        let dir2 = tempdir().unwrap();
+        make_tree(dir1.path());
+        make_tree(dir2.path());
+
+        let hasher = Hasher::new(HashAlgorithm::Sha256);
+        let options = ChecksumOptions::default();
+
+        let hash1 = hash_directory(dir1.path(), &hasher, &options).unwrap();
+        let hash2 = hash_directory(dir2.path(), &hasher, &options).unwrap();
+
+        assert_eq!(hash1, hash2);
+    }
+
+    #[test]
+    fn test_hash_directory_detects_content_change() {
+        let dir = tempdir().unwrap();
+        make_tree(dir.path());
+
+        let hasher = Hasher::new(HashAlgorithm::Sha256);
+        let options = ChecksumOptions::default();
+        let before = hash_directory(dir.path(), &hasher, &options).unwrap();
+
+        fs::write(dir.path().join("a.txt"), b"changed").unwrap();
+        let after = hash_directory(dir.path(), &hasher, &options).unwrap();
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn test_hash_directory_exclude_glob() {
+        let dir = tempdir().unwrap();
+        make_tree(dir.path());
+
+        let hasher = Hasher::new(HashAlgorithm::Sha256);
+        let with_exclude = ChecksumOptions {
+            exclude: vec!["sub/*".to_string()],
+            ..ChecksumOptions::default()
+        };
+        let without_sub = hash_directory(dir.path(), &hasher, &with_exclude).unwrap();
+
+        fs::remove_dir_all(dir.path().join("sub")).unwrap();
+        let reference = hash_directory(dir.path(), &hasher, &ChecksumOptions::default()).unwrap();
+
+        assert_eq!(without_sub, reference);
+    }
+
+    #[test]
+    fn test_hash_directory_ignore_hidden() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("visible.txt"), b"visible").unwrap();
+        fs::write(dir.path().join(".hidden"), b"hidden").unwrap();
+
+        let hasher = Hasher::new(HashAlgorithm::Sha256);
+        let options = ChecksumOptions {
+            ignore_hidden: true,
+            ..ChecksumOptions::default()
+        };
+        let with_hidden_ignored = hash_directory(dir.path(), &hasher, &options).unwrap();
+
+        fs::remove_file(dir.path().join(".hidden")).unwrap();
+        let reference = hash_directory(dir.path(), &hasher, &ChecksumOptions::default()).unwrap();
+
+        assert_eq!(with_hidden_ignored, reference);
+    }
+}
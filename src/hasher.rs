@@ -7,6 +7,8 @@ use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::Path;
 use rayon::prelude::*;
+use xxhash_rust::xxh3::Xxh3;
+use indicatif::ProgressBar;
 
 #[derive(Debug, Clone, Copy)]
 pub enum HashAlgorithm {
@@ -15,8 +17,109 @@ pub enum HashAlgorithm {
     Sha256,
     Sha512,
     Blake3,
+    Xxh3,
+    Crc32,
 }
 
+/// A streaming hash that can be fed bytes incrementally and finalized once.
+///
+/// This lets `hash_file_with_mode` run a single read loop for every
+/// algorithm instead of duplicating it per-variant.
+trait MyHasher {
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self: Box<Self>) -> String;
+}
+
+impl MyHasher for Md5 {
+    fn update(&mut self, data: &[u8]) {
+        Md5Digest::update(self, data);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        hex::encode(Md5Digest::finalize(*self))
+    }
+}
+
+impl MyHasher for Sha1 {
+    fn update(&mut self, data: &[u8]) {
+        Sha1Digest::update(self, data);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        hex::encode(Sha1Digest::finalize(*self))
+    }
+}
+
+impl MyHasher for Sha256 {
+    fn update(&mut self, data: &[u8]) {
+        Sha2Digest::update(self, data);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        hex::encode(Sha2Digest::finalize(*self))
+    }
+}
+
+impl MyHasher for Sha512 {
+    fn update(&mut self, data: &[u8]) {
+        Sha2Digest::update(self, data);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        hex::encode(Sha2Digest::finalize(*self))
+    }
+}
+
+impl MyHasher for blake3::Hasher {
+    fn update(&mut self, data: &[u8]) {
+        blake3::Hasher::update(self, data);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        blake3::Hasher::finalize(&self).to_hex().to_string()
+    }
+}
+
+impl MyHasher for Xxh3 {
+    fn update(&mut self, data: &[u8]) {
+        Xxh3::update(self, data);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:016x}", self.digest())
+    }
+}
+
+impl MyHasher for crc32fast::Hasher {
+    fn update(&mut self, data: &[u8]) {
+        crc32fast::Hasher::update(self, data);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:08x}", (*self).finalize())
+    }
+}
+
+impl HashAlgorithm {
+    fn hasher(&self) -> Box<dyn MyHasher> {
+        match self {
+            HashAlgorithm::Md5 => Box::new(Md5::new()),
+            HashAlgorithm::Sha1 => Box::new(Sha1::new()),
+            HashAlgorithm::Sha256 => Box::new(Sha256::new()),
+            HashAlgorithm::Sha512 => Box::new(Sha512::new()),
+            HashAlgorithm::Blake3 => Box::new(blake3::Hasher::new()),
+            HashAlgorithm::Xxh3 => Box::new(Xxh3::new()),
+            HashAlgorithm::Crc32 => Box::new(crc32fast::Hasher::new()),
+        }
+    }
+}
+
+/// Controls how much of a file `hash_file_with_mode` reads.
+///
+/// `Partial` is used to cheaply pre-filter duplicate candidates before
+/// committing to a full read; `Full` hashes the entire file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashMode {
+    Partial,
+    Full,
+}
+
+/// Number of leading bytes read from a file when hashing in `HashMode::Partial`.
+const PARTIAL_HASH_BLOCK_SIZE: u64 = 4096;
+
 pub struct Hasher {
     algorithm: HashAlgorithm,
 }
@@ -27,73 +130,142 @@ impl Hasher {
     }
 
     pub fn hash_file<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        self.hash_file_with_mode(path, HashMode::Full)
+    }
+
+    /// Hashes an already-in-memory sequence of byte-like items as a single
+    /// stream, finalizing once all items have been fed in. Used to fold many
+    /// per-file hashes into one combined digest (see `directory::hash_directory`).
+    pub fn hash_sequence<I, S>(&self, items: I) -> String
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<[u8]>,
+    {
+        let mut hasher = self.algorithm.hasher();
+        for item in items {
+            hasher.update(item.as_ref());
+        }
+        hasher.finalize()
+    }
+
+    pub fn hash_file_with_mode<P: AsRef<Path>>(&self, path: P, mode: HashMode) -> Result<String> {
         let file = File::open(path.as_ref())
             .context("Failed to open file")?;
         let mut reader = BufReader::new(file);
         let mut buffer = vec![0u8; 8192];
+        let mut remaining = match mode {
+            HashMode::Partial => PARTIAL_HASH_BLOCK_SIZE,
+            HashMode::Full => u64::MAX,
+        };
 
-        match self.algorithm {
-            HashAlgorithm::Md5 => {
-                let mut hasher = Md5::new();
-                loop {
-                    let n = reader.read(&mut buffer)?;
-                    if n == 0 { break; }
-                    hasher.update(&buffer[..n]);
-                }
-                Ok(hex::encode(hasher.finalize()))
-            }
-            HashAlgorithm::Sha1 => {
-                let mut hasher = Sha1::new();
-                loop {
-                    let n = reader.read(&mut buffer)?;
-                    if n == 0 { break; }
-                    hasher.update(&buffer[..n]);
-                }
-                Ok(hex::encode(hasher.finalize()))
-            }
-            HashAlgorithm::Sha256 => {
-                let mut hasher = Sha256::new();
-                loop {
-                    let n = reader.read(&mut buffer)?;
-                    if n == 0 { break; }
-                    hasher.update(&buffer[..n]);
-                }
-                Ok(hex::encode(hasher.finalize()))
-            }
-            HashAlgorithm::Sha512 => {
-                let mut hasher = Sha512::new();
-                loop {
-                    let n = reader.read(&mut buffer)?;
-                    if n == 0 { break; }
-                    hasher.update(&buffer[..n]);
+        let mut hasher = self.algorithm.hasher();
+        while remaining > 0 {
+            let to_read = (buffer.len() as u64).min(remaining) as usize;
+            let n = reader.read(&mut buffer[..to_read])?;
+            if n == 0 { break; }
+            hasher.update(&buffer[..n]);
+            remaining -= n as u64;
+        }
+        Ok(hasher.finalize())
+    }
+
+    /// Finds duplicate files among `paths` using a three-stage pipeline that
+    /// avoids fully reading files that can't possibly collide:
+    ///
+    /// 1. Group by file size — files with a unique size are dropped immediately.
+    /// 2. Within each size group, hash only the first `PARTIAL_HASH_BLOCK_SIZE`
+    ///    bytes (or the whole file, if it's smaller than that) to cheaply rule
+    ///    out most remaining candidates.
+    /// 3. Only paths that still collide on the partial hash get a full read.
+    ///
+    /// `progress`, if given, has its length set to the combined size of every
+    /// file with a readable size, then is incremented by that file's size
+    /// exactly once, at whichever stage finally decides its fate: immediately
+    /// for files dropped on a unique size, after the partial hash for files
+    /// that turn out not to collide (or fail to hash), or after the (more
+    /// expensive) full hash for files that reach stage 3.
+    pub fn find_duplicates<P: AsRef<Path> + Sync>(
+        &self,
+        paths: &[P],
+        progress: Option<&ProgressBar>,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let by_size: HashMap<u64, Vec<&Path>> = paths
+            .par_iter()
+            .map(|path| path.as_ref())
+            .fold(HashMap::new, |mut acc: HashMap<u64, Vec<&Path>>, path| {
+                if let Ok(meta) = std::fs::metadata(path) {
+                    acc.entry(meta.len()).or_default().push(path);
                 }
-                Ok(hex::encode(hasher.finalize()))
-            }
-            HashAlgorithm::Blake3 => {
-                let mut hasher = blake3::Hasher::new();
-                loop {
-                    let n = reader.read(&mut buffer)?;
-                    if n == 0 { break; }
-                    hasher.update(&buffer[..n]);
+                acc
+            })
+            .reduce(HashMap::new, |mut a, b| {
+                for (size, mut group) in b {
+                    a.entry(size).or_default().append(&mut group);
                 }
-                Ok(hasher.finalize().to_hex().to_string())
+                a
+            });
+
+        if let Some(bar) = progress {
+            let total_bytes: u64 = by_size.iter().map(|(size, group)| size * group.len() as u64).sum();
+            bar.set_length(total_bytes);
+        }
+
+        let mut candidates: Vec<(&Path, u64)> = Vec::new();
+        for (size, group) in by_size {
+            if group.len() > 1 {
+                candidates.extend(group.into_iter().map(|path| (path, size)));
+            } else if let Some(bar) = progress {
+                bar.inc(size * group.len() as u64);
             }
         }
-    }
 
-    pub fn find_duplicates<P: AsRef<Path>>(&self, paths: &[P]) -> Result<HashMap<String, Vec<String>>> {
-        let results: Vec<_> = paths.par_iter()
-            .filter_map(|path| {
-                let path_str = path.as_ref().display().to_string();
-                self.hash_file(path).ok().map(|hash| (hash, path_str))
+        let partial_results: Vec<(String, &Path, u64)> = candidates
+            .par_iter()
+            .filter_map(|&(path, size)| {
+                let mode = if size > PARTIAL_HASH_BLOCK_SIZE { HashMode::Partial } else { HashMode::Full };
+                let result = self.hash_file_with_mode(path, mode).ok().map(|hash| (hash, path, size));
+                if result.is_none() {
+                    if let Some(bar) = progress {
+                        bar.inc(size);
+                    }
+                }
+                result
             })
             .collect();
 
+        let mut by_partial: HashMap<String, Vec<(&Path, u64)>> = HashMap::new();
+        for (hash, path, size) in partial_results {
+            by_partial.entry(hash).or_default().push((path, size));
+        }
+
         let mut map: HashMap<String, Vec<String>> = HashMap::new();
-        for (hash, path) in results {
-            map.entry(hash).or_insert_with(Vec::new).push(path);
+        for (_, group) in by_partial {
+            if group.len() < 2 {
+                if let Some(bar) = progress {
+                    for &(_, size) in &group {
+                        bar.inc(size);
+                    }
+                }
+                continue;
+            }
+            let full_results: Vec<(String, String)> = group
+                .par_iter()
+                .filter_map(|&(path, size)| {
+                    let result = self
+                        .hash_file_with_mode(path, HashMode::Full)
+                        .ok()
+                        .map(|hash| (hash, path.display().to_string()));
+                    if let Some(bar) = progress {
+                        bar.inc(size);
+                    }
+                    result
+                })
+                .collect();
+            for (hash, path_str) in full_results {
+                map.entry(hash).or_default().push(path_str);
+            }
         }
-        
+
         Ok(map.into_iter().filter(|(_, v)| v.len() > 1).collect())
     }
 }
@@ -138,6 +310,30 @@ mod tests {
         assert_eq!(hash.len(), 64);
     }
 
+    #[test]
+    fn test_hash_file_xxh3() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "xxh3 test").unwrap();
+
+        let hasher = Hasher::new(HashAlgorithm::Xxh3);
+        let hash = hasher.hash_file(file.path()).unwrap();
+
+        assert_eq!(hash.len(), 16);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_hash_file_crc32() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "crc32 test").unwrap();
+
+        let hasher = Hasher::new(HashAlgorithm::Crc32);
+        let hash = hasher.hash_file(file.path()).unwrap();
+
+        assert_eq!(hash.len(), 8);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
     #[test]
     fn test_hash_file_nonexistent() {
         let hasher = Hasher::new(HashAlgorithm::Sha256);
@@ -158,7 +354,7 @@ mod tests {
         
         let hasher = Hasher::new(HashAlgorithm::Sha256);
         let paths = vec![file1.path(), file2.path(), file3.path()];
-        let duplicates = hasher.find_duplicates(&paths).unwrap();
+        let duplicates = hasher.find_duplicates(&paths, None).unwrap();
         
         assert_eq!(duplicates.len(), 1);
         let dup_files = duplicates.values().next().unwrap();
@@ -175,8 +371,65 @@ mod tests {
         
         let hasher = Hasher::new(HashAlgorithm::Sha256);
         let paths = vec![file1.path(), file2.path()];
-        let duplicates = hasher.find_duplicates(&paths).unwrap();
-        
+        let duplicates = hasher.find_duplicates(&paths, None).unwrap();
+
+        assert_eq!(duplicates.len(), 0);
+    }
+
+    #[test]
+    fn test_find_duplicates_unique_sizes_skip_read() {
+        let mut file1 = NamedTempFile::new().unwrap();
+        let mut file2 = NamedTempFile::new().unwrap();
+
+        writeln!(file1, "a").unwrap();
+        writeln!(file2, "bb").unwrap();
+
+        let hasher = Hasher::new(HashAlgorithm::Sha256);
+        let paths = vec![file1.path(), file2.path()];
+        let duplicates = hasher.find_duplicates(&paths, None).unwrap();
+
         assert_eq!(duplicates.len(), 0);
     }
+
+    #[test]
+    fn test_find_duplicates_zero_length_files() {
+        let file1 = NamedTempFile::new().unwrap();
+        let file2 = NamedTempFile::new().unwrap();
+
+        let hasher = Hasher::new(HashAlgorithm::Sha256);
+        let paths = vec![file1.path(), file2.path()];
+        let duplicates = hasher.find_duplicates(&paths, None).unwrap();
+
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates.values().next().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_find_duplicates_large_files_past_partial_block() {
+        let mut file1 = NamedTempFile::new().unwrap();
+        let mut file2 = NamedTempFile::new().unwrap();
+
+        let content = vec![b'x'; PARTIAL_HASH_BLOCK_SIZE as usize + 1024];
+        file1.write_all(&content).unwrap();
+        file2.write_all(&content).unwrap();
+
+        let hasher = Hasher::new(HashAlgorithm::Sha256);
+        let paths = vec![file1.path(), file2.path()];
+        let duplicates = hasher.find_duplicates(&paths, None).unwrap();
+
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates.values().next().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_hash_file_with_mode_partial_matches_full_for_small_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "short").unwrap();
+
+        let hasher = Hasher::new(HashAlgorithm::Sha256);
+        let partial = hasher.hash_file_with_mode(file.path(), HashMode::Partial).unwrap();
+        let full = hasher.hash_file_with_mode(file.path(), HashMode::Full).unwrap();
+
+        assert_eq!(partial, full);
+    }
 }
\ No newline at end of file
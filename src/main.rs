@@ -1,11 +1,14 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, ValueEnum};
 use colored::Colorize;
 use std::path::PathBuf;
 
+mod directory;
 mod hasher;
+mod progress;
 mod verifier;
 
+use directory::ChecksumOptions;
 use hasher::{HashAlgorithm, Hasher};
 use verifier::Verifier;
 
@@ -22,6 +25,9 @@ struct Cli {
     #[arg(short, long, help = "Verify against checksum (hash or file)")]
     verify: Option<String>,
 
+    #[arg(long, value_name = "SUMFILE", help = "Verify files listed in a checksum manifest (GNU or BSD format)")]
+    check: Option<PathBuf>,
+
     #[arg(short, long, help = "Recursive directory processing")]
     recursive: bool,
 
@@ -36,15 +42,32 @@ struct Cli {
 
     #[arg(long, help = "Read file list from stdin")]
     stdin: bool,
+
+    #[arg(long, help = "Compute one deterministic hash for an entire directory tree")]
+    directory_hash: bool,
+
+    #[arg(long = "exclude", value_name = "GLOB", help = "Glob pattern to exclude from --directory-hash (repeatable)")]
+    exclude: Vec<String>,
+
+    #[arg(long, help = "Skip dotfiles when computing --directory-hash")]
+    ignore_hidden: bool,
+
+    #[arg(long, help = "Follow symlinks when computing --directory-hash")]
+    follow_symlinks: bool,
+
+    #[arg(short, long, help = "Number of threads to use for parallel hashing")]
+    jobs: Option<usize>,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
 enum Algorithm {
     Md5,
     Sha1,
     Sha256,
     Sha512,
     Blake3,
+    Xxh3,
+    Crc32,
 }
 
 impl From<Algorithm> for HashAlgorithm {
@@ -55,6 +78,8 @@ impl From<Algorithm> for HashAlgorithm {
             Algorithm::Sha256 => HashAlgorithm::Sha256,
             Algorithm::Sha512 => HashAlgorithm::Sha512,
             Algorithm::Blake3 => HashAlgorithm::Blake3,
+            Algorithm::Xxh3 => HashAlgorithm::Xxh3,
+            Algorithm::Crc32 => HashAlgorithm::Crc32,
         }
     }
 }
@@ -70,6 +95,50 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
     let hasher = Hasher::new(cli.algorithm.into());
 
+    if let Some(jobs) = cli.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .context("Failed to configure thread pool")?;
+    }
+
+    if let Some(sumfile) = &cli.check {
+        let outcomes = verifier::check_manifest(sumfile, cli.algorithm.into(), cli.quiet)?;
+        let mut failures = 0usize;
+
+        for outcome in &outcomes {
+            match &outcome.result {
+                Ok(true) => println!("{}: {}", outcome.path, "OK".green()),
+                Ok(false) => {
+                    failures += 1;
+                    println!("{}: {}", outcome.path, "FAILED".red());
+                }
+                Err(e) => {
+                    failures += 1;
+                    println!("{}: {} - {}", outcome.path, "FAILED".red(), e);
+                }
+            }
+        }
+
+        if failures > 0 {
+            println!("\n{}", format!("{} of {} computed checksums did NOT match", failures, outcomes.len()).red());
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if cli.directory_hash {
+        let root = cli.paths.first().context("No directory specified for --directory-hash")?;
+        let options = ChecksumOptions {
+            exclude: cli.exclude.clone(),
+            ignore_hidden: cli.ignore_hidden,
+            follow_symlinks: cli.follow_symlinks,
+        };
+        let hash = directory::hash_directory(root, &hasher, &options)?;
+        println!("{}", hash);
+        return Ok(());
+    }
+
     if let Some(checksum) = cli.verify {
         let verifier = Verifier::new(hasher);
         if cli.paths.is_empty() {
@@ -97,11 +166,14 @@ fn main() -> Result<()> {
     } else {
         for path in &cli.paths {
             if path.is_dir() && cli.recursive {
+                let spinner = progress::discovery_spinner(cli.quiet);
                 for entry in walkdir::WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
                     if entry.file_type().is_file() {
                         files.push(entry.path().to_path_buf());
+                        spinner.inc(1);
                     }
                 }
+                spinner.finish_and_clear();
             } else if path.is_file() {
                 files.push(path.clone());
             }
@@ -109,19 +181,26 @@ fn main() -> Result<()> {
     }
 
     if cli.duplicates {
-        let duplicates = hasher.find_duplicates(&files)?;
+        let bar = progress::byte_bar(0, cli.quiet);
+        let duplicates = hasher.find_duplicates(&files, Some(&bar))?;
+        bar.finish_and_clear();
         for (hash, paths) in duplicates {
             if paths.len() > 1 {
                 println!("\n{} ({})", "Duplicate files:".yellow(), hash);
                 for path in paths {
-                    println!("  {}", path.display());
+                    println!("  {}", path);
                 }
             }
         }
         return Ok(());
     }
 
-    for path in files {
+    let sizes: Vec<u64> = files
+        .iter()
+        .map(|path| std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0))
+        .collect();
+    let bar = progress::byte_bar(sizes.iter().sum(), cli.quiet);
+    for (path, size) in files.into_iter().zip(sizes) {
         match hasher.hash_file(&path) {
             Ok(hash) => {
                 if cli.quiet {
@@ -129,9 +208,9 @@ fn main() -> Result<()> {
                 } else {
                     match cli.format {
                         OutputFormat::Plain => println!("{} {}", hash, path.display()),
-                        OutputFormat::Bsd => println!("{}({}) = {}", 
-                            format!("{:?}", cli.algorithm).to_uppercase(), 
-                            path.display(), 
+                        OutputFormat::Bsd => println!("{} ({}) = {}",
+                            format!("{:?}", cli.algorithm).to_uppercase(),
+                            path.display(),
                             hash
                         ),
                         OutputFormat::Gnu => println!("{} *{}", hash, path.display()),
@@ -140,7 +219,9 @@ fn main() -> Result<()> {
             }
             Err(e) => eprintln!("{}: {}", path.display(), e.to_string().red()),
         }
+        bar.inc(size);
     }
+    bar.finish_and_clear();
 
     Ok(())
 }
\ No newline at end of file
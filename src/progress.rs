@@ -0,0 +1,39 @@
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+
+/// Whether a progress indicator should actually render: stdout must be a
+/// terminal and the user must not have asked for quiet output.
+pub fn should_show(quiet: bool) -> bool {
+    !quiet && std::io::stdout().is_terminal()
+}
+
+/// A bar tracking bytes processed, with throughput and ETA.
+/// Used for the main hashing loop, the duplicate-detection pipeline, and
+/// `--check`. The caller increments it by each file's size (not by 1 per
+/// file), so `{bytes_per_sec}` reflects actual throughput instead of a
+/// files-per-second rate mislabeled with byte units.
+pub fn byte_bar(total_bytes: u64, quiet: bool) -> ProgressBar {
+    if !should_show(quiet) {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new(total_bytes);
+    bar.set_style(
+        ProgressStyle::with_template(
+            "{bar:40.cyan/blue} {bytes}/{total_bytes} ({bytes_per_sec}, ETA {eta})",
+        )
+        .unwrap(),
+    );
+    bar
+}
+
+/// A spinner for the directory-walk discovery phase, before the total file
+/// count is known. Returns a hidden bar (no-op) when progress shouldn't be shown.
+pub fn discovery_spinner(quiet: bool) -> ProgressBar {
+    if !should_show(quiet) {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new_spinner();
+    bar.set_style(ProgressStyle::with_template("{spinner} discovering files... {pos}").unwrap());
+    bar.enable_steady_tick(std::time::Duration::from_millis(100));
+    bar
+}
@@ -1,6 +1,9 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::fs;
 use std::path::Path;
-use crate::hasher::Hasher;
+use rayon::prelude::*;
+use crate::hasher::{HashAlgorithm, Hasher};
+use crate::progress;
 
 pub struct Verifier {
     hasher: Hasher,
@@ -17,6 +20,115 @@ impl Verifier {
     }
 }
 
+/// One entry parsed out of a GNU- or BSD-style checksum manifest.
+pub struct ChecksumEntry {
+    pub path: String,
+    pub expected_hash: String,
+    /// `Some` only for BSD-tagged lines, which name their algorithm inline.
+    /// GNU lines carry no algorithm and fall back to the caller's default.
+    pub algorithm: Option<HashAlgorithm>,
+}
+
+/// The outcome of checking a single manifest entry against the file on disk.
+pub struct CheckOutcome {
+    pub path: String,
+    pub result: Result<bool>,
+}
+
+/// Parses every non-blank line of a checksum manifest, skipping lines that
+/// match neither the GNU nor the BSD format instead of failing the whole file.
+pub fn parse_manifest(contents: &str) -> Vec<ChecksumEntry> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(parse_manifest_line)
+        .collect()
+}
+
+fn parse_manifest_line(line: &str) -> Option<ChecksumEntry> {
+    parse_bsd_line(line).or_else(|| parse_gnu_line(line))
+}
+
+/// `SHA256 (path/to/file) = <hash>`
+fn parse_bsd_line(line: &str) -> Option<ChecksumEntry> {
+    let (tag, rest) = line.split_once(" (")?;
+    let (path, hash) = rest.split_once(") = ")?;
+    let algorithm = algorithm_from_tag(tag.trim())?;
+    Some(ChecksumEntry {
+        path: path.trim().to_string(),
+        expected_hash: hash.trim().to_string(),
+        algorithm: Some(algorithm),
+    })
+}
+
+/// `<hash> *path/to/file` or `<hash>  path/to/file`
+fn parse_gnu_line(line: &str) -> Option<ChecksumEntry> {
+    let (hash, rest) = line.split_once(char::is_whitespace)?;
+    let path = rest.trim_start().trim_start_matches('*').trim();
+    if hash.is_empty() || path.is_empty() {
+        return None;
+    }
+    Some(ChecksumEntry {
+        path: path.to_string(),
+        expected_hash: hash.to_string(),
+        algorithm: None,
+    })
+}
+
+fn algorithm_from_tag(tag: &str) -> Option<HashAlgorithm> {
+    match tag.to_ascii_uppercase().as_str() {
+        "MD5" => Some(HashAlgorithm::Md5),
+        "SHA1" => Some(HashAlgorithm::Sha1),
+        "SHA256" => Some(HashAlgorithm::Sha256),
+        "SHA512" => Some(HashAlgorithm::Sha512),
+        "BLAKE3" => Some(HashAlgorithm::Blake3),
+        "XXH3" => Some(HashAlgorithm::Xxh3),
+        "CRC32" => Some(HashAlgorithm::Crc32),
+        _ => None,
+    }
+}
+
+/// Verifies every entry in a checksum manifest against the files on disk,
+/// mirroring `sha256sum -c`. Entries are checked in parallel; GNU-format
+/// entries (which carry no algorithm tag) are hashed with `default_algorithm`.
+///
+/// The manifest is fully parsed before checking starts, so the total size of
+/// every entry's file is known up front and a bytes-processed progress bar
+/// tracks the parallel verification (rather than a spinner, since unlike a
+/// directory walk the total here isn't open-ended). The bar is hidden when
+/// `quiet` is set or stdout is not a terminal.
+pub fn check_manifest<P: AsRef<Path>>(
+    sumfile: P,
+    default_algorithm: HashAlgorithm,
+    quiet: bool,
+) -> Result<Vec<CheckOutcome>> {
+    let contents = fs::read_to_string(sumfile.as_ref()).context("Failed to read checksum file")?;
+    let entries = parse_manifest(&contents);
+    let total_bytes: u64 = entries
+        .iter()
+        .filter_map(|entry| fs::metadata(&entry.path).ok())
+        .map(|meta| meta.len())
+        .sum();
+    let bar = progress::byte_bar(total_bytes, quiet);
+
+    let outcomes = entries
+        .par_iter()
+        .map(|entry| {
+            let verifier = Verifier::new(Hasher::new(entry.algorithm.unwrap_or(default_algorithm)));
+            let outcome = CheckOutcome {
+                path: entry.path.clone(),
+                result: verifier.verify_file(&entry.path, &entry.expected_hash),
+            };
+            let size = fs::metadata(&entry.path).map(|meta| meta.len()).unwrap_or(0);
+            bar.inc(size);
+            outcome
+        })
+        .collect();
+
+    bar.finish_and_clear();
+    Ok(outcomes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,7 +197,88 @@ mod tests {
         let hasher = Hasher::new(HashAlgorithm::Sha256);
         let verifier = Verifier::new(hasher);
         let result = verifier.verify_file("/nonexistent/file.txt", "somehash");
-        
+
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_manifest_gnu_format() {
+        let manifest = "abc123  path/one.txt\ndef456 *path/two.txt\n";
+        let entries = parse_manifest(manifest);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "path/one.txt");
+        assert_eq!(entries[0].expected_hash, "abc123");
+        assert!(entries[0].algorithm.is_none());
+        assert_eq!(entries[1].path, "path/two.txt");
+        assert_eq!(entries[1].expected_hash, "def456");
+    }
+
+    #[test]
+    fn test_parse_manifest_bsd_format() {
+        let manifest = "SHA256 (path/to/file.txt) = abc123def456\n";
+        let entries = parse_manifest(manifest);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "path/to/file.txt");
+        assert_eq!(entries[0].expected_hash, "abc123def456");
+        assert!(matches!(entries[0].algorithm, Some(HashAlgorithm::Sha256)));
+    }
+
+    #[test]
+    fn test_parse_manifest_skips_blank_and_malformed_lines() {
+        let manifest = "\n   \nnotavalidline\n";
+        let entries = parse_manifest(manifest);
+
+        assert_eq!(entries.len(), 0);
+    }
+
+    #[test]
+    fn test_check_manifest_gnu() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "check manifest test").unwrap();
+
+        let hasher = Hasher::new(HashAlgorithm::Sha256);
+        let hash = hasher.hash_file(file.path()).unwrap();
+
+        let mut sumfile = NamedTempFile::new().unwrap();
+        writeln!(sumfile, "{}  {}", hash, file.path().display()).unwrap();
+
+        let outcomes = check_manifest(sumfile.path(), HashAlgorithm::Sha256, true).unwrap();
+
+        assert_eq!(outcomes.len(), 1);
+        assert!(outcomes[0].result.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_check_manifest_bsd_uses_tagged_algorithm() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "bsd check test").unwrap();
+
+        let hasher = Hasher::new(HashAlgorithm::Blake3);
+        let hash = hasher.hash_file(file.path()).unwrap();
+
+        let mut sumfile = NamedTempFile::new().unwrap();
+        writeln!(sumfile, "BLAKE3 ({}) = {}", file.path().display(), hash).unwrap();
+
+        // default_algorithm is deliberately wrong to prove the BSD tag wins.
+        let outcomes = check_manifest(sumfile.path(), HashAlgorithm::Sha256, true).unwrap();
+
+        assert_eq!(outcomes.len(), 1);
+        assert!(outcomes[0].result.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_check_manifest_reports_failure() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "mismatch test").unwrap();
+
+        let mut sumfile = NamedTempFile::new().unwrap();
+        writeln!(sumfile, "deadbeef  {}", file.path().display()).unwrap();
+
+        let outcomes = check_manifest(sumfile.path(), HashAlgorithm::Sha256, true).unwrap();
+
+        assert_eq!(outcomes.len(), 1);
+        assert!(!outcomes[0].result.as_ref().unwrap());
+    }
 }
\ No newline at end of file
@@ -41,4 +41,52 @@ fn test_verification() {
     assert!(verify_output.status.success());
     let stdout = String::from_utf8_lossy(&verify_output.stdout);
     assert!(stdout.contains("OK"));
+}
+
+#[test]
+fn test_bsd_format_round_trips_through_check() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("roundtrip.txt");
+    let mut file = File::create(&file_path).unwrap();
+    writeln!(file, "Round trip content").unwrap();
+
+    let hash_output = std::process::Command::new("cargo")
+        .args(["run", "--", "--format", "bsd", file_path.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(hash_output.status.success());
+
+    let sumfile_path = dir.path().join("checksums.bsd");
+    std::fs::write(&sumfile_path, hash_output.stdout).unwrap();
+
+    let check_output = std::process::Command::new("cargo")
+        .args(["run", "--", "--check", sumfile_path.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    assert!(check_output.status.success());
+    let stdout = String::from_utf8_lossy(&check_output.stdout);
+    assert!(stdout.contains("OK"));
+}
+
+#[test]
+fn test_check_reports_failure_and_exits_non_zero() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("check.txt");
+    let mut file = File::create(&file_path).unwrap();
+    writeln!(file, "Check content").unwrap();
+
+    let sumfile_path = dir.path().join("checksums.sha256");
+    let mut sumfile = File::create(&sumfile_path).unwrap();
+    writeln!(sumfile, "deadbeef  {}", file_path.to_str().unwrap()).unwrap();
+
+    let output = std::process::Command::new("cargo")
+        .args(["run", "--", "--check", sumfile_path.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("FAILED"));
+    assert!(stdout.contains("1 of 1 computed checksums did NOT match"));
 }
\ No newline at end of file